@@ -0,0 +1,221 @@
+//! A generic transport over any `AsyncRead + AsyncWrite` byte stream
+//!
+//! Rather than hardcoding a USB or serial backend, [`HostClient::new_io()`] wraps
+//! any `tokio::io::AsyncRead + AsyncWrite` (a TCP socket, TLS stream, a PTY, a
+//! child process' stdio, ...) and frames it with a pluggable [`Decoder`]/
+//! [`Encoder`] codec, turning the crate's transport layer into an open
+//! extension point instead of a fixed enumeration of backends.
+//!
+//! Two codecs are provided out of the box:
+//!
+//! * [`CobsCodec`]: the same COBS framing used by the `serial` transport.
+//! * [`LengthDelimitedCodec`]: a 4-byte little-endian length prefix followed
+//!   by [`RpcFrame::to_bytes()`].
+
+use futures_util::{SinkExt, StreamExt};
+use postcard::experimental::schema::Schema;
+use serde::de::DeserializeOwned;
+use tokio::io::{AsyncRead, AsyncWrite};
+pub use tokio_util::codec::{Decoder, Encoder};
+use tokio_util::{
+    bytes::{Buf, BufMut, BytesMut},
+    codec::Framed,
+};
+
+use super::{HostClient, RpcFrame, SubInfo, TaskHandle, WireContext};
+use crate::WireHeader;
+
+impl<WireErr> HostClient<WireErr>
+where
+    WireErr: DeserializeOwned + Schema,
+{
+    /// Create a new [HostClient] that drives its I/O over any
+    /// `AsyncRead + AsyncWrite` byte stream, framed by `codec`.
+    ///
+    /// The read half feeds [`HostContext::process_did_wake`][super::HostContext::process_did_wake]
+    /// and the write half drains the outgoing queue, same as the built-in
+    /// USB/serial transports.
+    pub fn new_io<T, C>(io: T, codec: C, err_uri_path: &str, outgoing_depth: usize) -> Self
+    where
+        T: AsyncRead + AsyncWrite + Send + Unpin + 'static,
+        C: Decoder<Item = RpcFrame> + Encoder<RpcFrame> + Send + Unpin + 'static,
+        <C as Decoder>::Error: From<std::io::Error> + std::fmt::Debug,
+        <C as Encoder<RpcFrame>>::Error: From<std::io::Error> + std::fmt::Debug,
+    {
+        let (me, wire) = Self::new_manual(err_uri_path, outgoing_depth);
+        let handle = tokio::spawn(run(Framed::new(io, codec), wire));
+        me.ctx.register_task(TaskHandle::Tokio(handle));
+        me
+    }
+}
+
+async fn run<T, C>(mut framed: Framed<T, C>, wire: WireContext)
+where
+    T: AsyncRead + AsyncWrite + Unpin,
+    C: Decoder<Item = RpcFrame> + Encoder<RpcFrame> + Unpin,
+    <C as Decoder>::Error: From<std::io::Error> + std::fmt::Debug,
+    <C as Encoder<RpcFrame>>::Error: From<std::io::Error> + std::fmt::Debug,
+{
+    let WireContext {
+        mut outgoing,
+        incoming,
+        mut new_subs,
+    } = wire;
+    let mut subs: Vec<SubInfo> = Vec::new();
+
+    loop {
+        tokio::select! {
+            _ = incoming.wait_for_shutdown() => {
+                return;
+            }
+            frame = outgoing.recv() => {
+                let Some(frame) = frame else {
+                    incoming.set_disconnect_reason(super::DisconnectReason::AllClientsDropped);
+                    return;
+                };
+                if let Err(e) = framed.send(frame).await {
+                    incoming.set_disconnect_reason(super::DisconnectReason::Io(format!("{e:?}")));
+                    return;
+                }
+            }
+            sub = new_subs.recv() => {
+                let Some(sub) = sub else {
+                    incoming.set_disconnect_reason(super::DisconnectReason::AllClientsDropped);
+                    return;
+                };
+                subs.push(sub);
+            }
+            frame = framed.next() => {
+                let frame = match frame {
+                    Some(Ok(frame)) => frame,
+                    Some(Err(e)) => {
+                        incoming.set_disconnect_reason(super::DisconnectReason::Decode(format!("{e:?}")));
+                        return;
+                    }
+                    None => {
+                        incoming.set_disconnect_reason(super::DisconnectReason::Io("end of stream".into()));
+                        return;
+                    }
+                };
+                let header = frame.header.clone();
+                let body = frame.body.clone();
+                if matches!(incoming.process_did_wake(frame), Ok(false) | Err(_)) {
+                    subs.retain(|sub| {
+                        sub.key != header.key
+                            || sub
+                                .tx
+                                .try_send(RpcFrame {
+                                    header: header.clone(),
+                                    body: body.clone(),
+                                })
+                                .is_ok()
+                    });
+                }
+            }
+        }
+    }
+}
+
+/// Largest frame [LengthDelimitedCodec] will allocate for when decoding.
+///
+/// Bounds the length prefix read off the wire before trusting it enough to
+/// `reserve()` a buffer for it, so a corrupt or adversarial peer can't force
+/// a multi-gigabyte allocation attempt with a single 4-byte prefix.
+const MAX_FRAME_LEN: usize = 16 * 1024 * 1024;
+
+/// Error returned by [CobsCodec] and [LengthDelimitedCodec].
+#[derive(Debug)]
+pub enum CodecError {
+    /// The underlying I/O operation failed.
+    Io(std::io::Error),
+    /// COBS decoding failed.
+    Cobs,
+    /// The frame's [WireHeader] failed to deserialize.
+    Postcard(postcard::Error),
+    /// [LengthDelimitedCodec] read a length prefix larger than [MAX_FRAME_LEN].
+    FrameTooLarge(usize),
+}
+
+impl From<std::io::Error> for CodecError {
+    fn from(value: std::io::Error) -> Self {
+        Self::Io(value)
+    }
+}
+
+fn take_rpc_frame(data: &[u8]) -> Result<RpcFrame, CodecError> {
+    let (header, body) =
+        postcard::take_from_bytes::<WireHeader>(data).map_err(CodecError::Postcard)?;
+    Ok(RpcFrame {
+        header,
+        body: body.to_vec(),
+    })
+}
+
+/// COBS-delimited framing, the same scheme the `serial` transport uses,
+/// factored out here so it can be reused by any `new_io` caller.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct CobsCodec;
+
+impl Decoder for CobsCodec {
+    type Item = RpcFrame;
+    type Error = CodecError;
+
+    fn decode(&mut self, src: &mut BytesMut) -> Result<Option<Self::Item>, Self::Error> {
+        let Some(zero_pos) = src.iter().position(|b| *b == 0) else {
+            return Ok(None);
+        };
+        let mut encoded = src.split_to(zero_pos + 1);
+        encoded.truncate(zero_pos);
+        let decoded = cobs::decode_vec(&encoded).map_err(|_| CodecError::Cobs)?;
+        take_rpc_frame(&decoded).map(Some)
+    }
+}
+
+impl Encoder<RpcFrame> for CobsCodec {
+    type Error = CodecError;
+
+    fn encode(&mut self, item: RpcFrame, dst: &mut BytesMut) -> Result<(), Self::Error> {
+        let raw = item.to_bytes();
+        let mut encoded = cobs::encode_vec(&raw);
+        encoded.push(0);
+        dst.extend_from_slice(&encoded);
+        Ok(())
+    }
+}
+
+/// A 4-byte little-endian length prefix followed by [`RpcFrame::to_bytes()`].
+#[derive(Debug, Default, Clone, Copy)]
+pub struct LengthDelimitedCodec;
+
+impl Decoder for LengthDelimitedCodec {
+    type Item = RpcFrame;
+    type Error = CodecError;
+
+    fn decode(&mut self, src: &mut BytesMut) -> Result<Option<Self::Item>, Self::Error> {
+        if src.len() < 4 {
+            return Ok(None);
+        }
+        let len = u32::from_le_bytes(src[..4].try_into().unwrap()) as usize;
+        if len > MAX_FRAME_LEN {
+            return Err(CodecError::FrameTooLarge(len));
+        }
+        if src.len() < 4 + len {
+            src.reserve(4 + len - src.len());
+            return Ok(None);
+        }
+        src.advance(4);
+        let raw = src.split_to(len);
+        take_rpc_frame(&raw).map(Some)
+    }
+}
+
+impl Encoder<RpcFrame> for LengthDelimitedCodec {
+    type Error = CodecError;
+
+    fn encode(&mut self, item: RpcFrame, dst: &mut BytesMut) -> Result<(), Self::Error> {
+        let raw = item.to_bytes();
+        dst.put_u32_le(raw.len() as u32);
+        dst.extend_from_slice(&raw);
+        Ok(())
+    }
+}
@@ -5,10 +5,11 @@
 
 use core::future::Future;
 use std::{
+    collections::HashMap,
     marker::PhantomData,
     sync::{
         atomic::{AtomicU32, Ordering},
-        Arc,
+        Arc, Mutex, OnceLock,
     },
 };
 
@@ -21,6 +22,15 @@ mod serial;
 #[cfg(feature = "webusb")]
 pub mod webusb;
 
+#[cfg(feature = "ws")]
+mod ws;
+
+#[cfg(feature = "io")]
+pub mod io;
+
+#[cfg(feature = "quic")]
+mod quic;
+
 mod util;
 
 use maitake_sync::{
@@ -33,6 +43,7 @@ use tokio::{
     select,
     sync::mpsc::{Receiver, Sender},
 };
+use tokio_util::sync::CancellationToken;
 
 use crate::{Endpoint, Key, Topic, WireHeader};
 
@@ -48,6 +59,8 @@ pub enum HostErr<WireErr> {
     Postcard(postcard::Error),
     /// The interface has been closed, and no further messages are possible
     Closed,
+    /// The interface has been closed, and we know why
+    Disconnected(Arc<DisconnectReason>),
 }
 
 impl<T> From<postcard::Error> for HostErr<T> {
@@ -62,6 +75,23 @@ impl<T> From<WaitError> for HostErr<T> {
     }
 }
 
+/// Why the I/O worker stopped, and the link it was driving is now dead.
+///
+/// Kept as plain text rather than the transport's native error type, since
+/// [HostContext] (and therefore [HostErr]) isn't generic over a particular
+/// transport.
+#[derive(Debug, Clone, PartialEq)]
+pub enum DisconnectReason {
+    /// The transport's underlying I/O returned an error.
+    Io(String),
+    /// A frame coming off the wire failed to decode.
+    Decode(String),
+    /// All [HostClient]s were dropped and the worker shut down cleanly.
+    AllClientsDropped,
+    /// [`HostClient::shutdown`] or [`HostClient::close`] was called.
+    Shutdown,
+}
+
 pub trait Client: Clone + 'static {
     type Error; // or std?
     async fn receive(&self) -> Result<Vec<u8>, Self::Error>;
@@ -69,8 +99,34 @@ pub trait Client: Clone + 'static {
     // TODO:
     // 1. tokio::task::spawn requires `Send`, but the webusb futures aren't send.
     // can't fix this with #[trait_variant::make(Client: Send)] sadly…
-    // 2. no task handles at all are a bit meh
-    fn spawn(&self, fut: impl Future<Output = ()> + 'static);
+    /// Spawn `fut` as the transport's I/O task, returning a handle so
+    /// [`HostClient::shutdown`]/[`HostClient::close`] have something to await or abort.
+    fn spawn(&self, fut: impl Future<Output = ()> + 'static) -> TaskHandle;
+}
+
+/// An abortable handle to a task spawned by a [Client] or a transport's own I/O worker.
+pub enum TaskHandle {
+    /// A task spawned with `tokio::task::spawn`.
+    Tokio(tokio::task::JoinHandle<()>),
+    /// A task spawned with `wasm_bindgen_futures::spawn_local`, which offers no
+    /// way to await or abort it, so there is nothing to hold onto but its existence.
+    Wasm,
+}
+
+impl TaskHandle {
+    /// Abort the task, if it is abortable.
+    pub fn abort(&self) {
+        if let TaskHandle::Tokio(handle) = self {
+            handle.abort();
+        }
+    }
+
+    /// Wait for the task to finish, if it is awaitable.
+    pub async fn join(self) {
+        if let TaskHandle::Tokio(handle) = self {
+            let _ = handle.await;
+        }
+    }
 }
 
 /// The [HostClient] is the primary PC-side interface.
@@ -81,10 +137,15 @@ pub trait Client: Clone + 'static {
 ///
 /// [HostClient]s can be cloned, and used across multiple tasks/threads.
 ///
-/// There are currently two ways to create one, based on the transport used:
+/// There are currently several ways to create one, based on the transport used:
 ///
 /// 1. With raw USB Bulk transfers: [`HostClient::new_raw_nusb()`] (**recommended**)
 /// 2. With cobs CDC-ACM transfers: [`HostClient::new_serial_cobs()`]
+/// 3. With a WebSocket connection: [`HostClient::new_ws()`] (requires the `ws` feature)
+/// 4. With any `AsyncRead + AsyncWrite` byte stream and a pluggable framing
+///    codec: `HostClient::new_io()` (requires the `io` feature)
+/// 5. With a QUIC connection, one bidirectional stream per request:
+///    `HostClient::new_quic()` (requires the `quic` feature)
 pub struct HostClient<WireErr> {
     ctx: Arc<HostContext>,
     out: Sender<RpcFrame>,
@@ -113,6 +174,12 @@ where
         let ctx = Arc::new(HostContext {
             map: WaitMap::new(),
             seq: AtomicU32::new(0),
+            streams: Mutex::new(HashMap::new()),
+            disconnect: OnceLock::new(),
+            shutdown: CancellationToken::new(),
+            handles: Mutex::new(Vec::new()),
+            one_way: Mutex::new(HashMap::new()),
+            pending: Mutex::new(HashMap::new()),
         });
 
         let err_key = Key::for_path::<WireErr>(err_uri_path);
@@ -140,6 +207,15 @@ impl<WireErr> HostClient<WireErr>
 where
     WireErr: DeserializeOwned + Schema,
 {
+    /// Build the error to report when the link to the I/O worker is gone,
+    /// preferring the worker's own [DisconnectReason] over a bare [HostErr::Closed].
+    fn link_closed_err(&self) -> HostErr<WireErr> {
+        match self.ctx.disconnect_reason() {
+            Some(reason) => HostErr::Disconnected(reason),
+            None => HostErr::Closed,
+        }
+    }
+
     /// Send a message of type [Endpoint::Request][Endpoint] to `path`, and await
     /// a response of type [Endpoint::Response][Endpoint] (or WireErr) to `path`.
     ///
@@ -161,28 +237,39 @@ where
             },
             body: msg,
         };
-        self.out.send(frame).await.map_err(|_| HostErr::Closed)?;
-        let ok_resp = self.ctx.map.wait(WireHeader {
+        self.out
+            .send(frame)
+            .await
+            .map_err(|_| self.link_closed_err())?;
+        let resp_header = WireHeader {
             seq_no,
             key: E::RESP_KEY,
-        });
+        };
+        // So a transport that reconnects (e.g. `ws`) can fail this call
+        // promptly on a dropped link, instead of leaving it to hang until a
+        // reply that may never come.
+        let fail = self.ctx.register_pending(seq_no);
+        let ok_resp = self.ctx.map.wait(resp_header);
         let err_resp = self.ctx.map.wait(WireHeader {
             seq_no,
             key: self.err_key,
         });
 
-        select! {
+        let result = select! {
             o = ok_resp => {
-                let resp = o?;
+                let resp = o.map_err(|_| self.link_closed_err())?;
                 let r = postcard::from_bytes::<E::Response>(&resp)?;
                 Ok(r)
             },
             e = err_resp => {
-                let resp = e?;
+                let resp = e.map_err(|_| self.link_closed_err())?;
                 let r = postcard::from_bytes::<WireErr>(&resp)?;
                 Err(HostErr::Wire(r))
             },
-        }
+            _ = fail => Err(self.link_closed_err()),
+        };
+        self.ctx.unregister_pending(seq_no);
+        result
     }
 
     /// Send a message of dynamically typed `req_schema` [NamedType] to an endpoint specified by `req_key` [Key], and await
@@ -207,49 +294,149 @@ where
             },
             body: msg,
         };
-        self.out.send(frame).await.map_err(|_| HostErr::Closed)?;
-        let ok_resp = self.ctx.map.wait(WireHeader {
+        self.out
+            .send(frame)
+            .await
+            .map_err(|_| self.link_closed_err())?;
+        let resp_header = WireHeader {
             seq_no,
             key: resp_key,
-        });
+        };
+        // So a transport that reconnects (e.g. `ws`) can fail this call
+        // promptly on a dropped link, instead of leaving it to hang until a
+        // reply that may never come.
+        let fail = self.ctx.register_pending(seq_no);
+        let ok_resp = self.ctx.map.wait(resp_header);
         let err_resp = self.ctx.map.wait(WireHeader {
             seq_no,
             key: self.err_key,
         });
 
-        select! {
+        let result = select! {
             o = ok_resp => {
-                let resp = o?;
+                let resp = o.map_err(|_| self.link_closed_err())?;
                 // TODO proper error handling
                 let r = postcard_dyn::from_slice_dyn(resp_schema, &resp).expect("deser error");
                 Ok(r)
             },
             e = err_resp => {
                 // TODO proper error handling
-                let resp = e?;
+                let resp = e.map_err(|_| self.link_closed_err())?;
                 let r = postcard::from_bytes::<WireErr>(&resp)?;
                 Err(HostErr::Wire(r))
             },
+            _ = fail => Err(self.link_closed_err()),
+        };
+        self.ctx.unregister_pending(seq_no);
+        result
+    }
+
+    /// Send a message of type [Endpoint::Request][Endpoint] to `path`, and return a
+    /// [StreamHandle] that yields the [Endpoint::Response][Endpoint]s the server
+    /// pushes back for this one request, instead of completing on the first reply.
+    ///
+    /// This is useful for server-push results like log tailing, progressive file
+    /// transfer, or telemetry bursts. The server ends the stream either by replying
+    /// on the error key (the handle's last item is then [HostErr::Wire], or
+    /// [HostErr::Postcard] if that reply doesn't decode) or by sending an empty
+    /// terminal frame on the response key.
+    ///
+    /// Incoming chunks are handed to the handle without waiting: a chunk
+    /// (including the error-key reply) that arrives while `depth` chunks are
+    /// already buffered is dropped.
+    pub async fn send_resp_stream<E: Endpoint>(
+        &self,
+        t: &E::Request,
+        depth: usize,
+    ) -> Result<StreamHandle<E::Response, WireErr>, HostErr<WireErr>>
+    where
+        E::Request: Serialize + Schema,
+        E::Response: DeserializeOwned + Schema,
+    {
+        let seq_no = self.ctx.seq.fetch_add(1, Ordering::Relaxed);
+        let msg = postcard::to_stdvec(&t).expect("Allocations should not ever fail");
+        let frame: RpcFrame = RpcFrame {
+            header: WireHeader {
+                key: E::REQ_KEY,
+                seq_no,
+            },
+            body: msg,
+        };
+        let resp_header = WireHeader {
+            seq_no,
+            key: E::RESP_KEY,
+        };
+        let err_header = WireHeader {
+            seq_no,
+            key: self.err_key,
+        };
+        let (tx, rx) = tokio::sync::mpsc::channel(depth);
+        self.out.send(frame).await.map_err(|_| self.link_closed_err())?;
+        // Registered only now that the send has actually gone out: if it had
+        // failed above, there'd be nobody left to ever remove this entry
+        // (that's normally `StreamHandle`'s `Drop`, but it's never
+        // constructed on this error path), leaking it for the `HostContext`'s
+        // lifetime.
+        self.ctx
+            .register_stream(resp_header.clone(), err_header.clone(), tx);
+        Ok(StreamHandle {
+            ctx: self.ctx.clone(),
+            resp_header,
+            err_header,
+            rx,
+            _pd: PhantomData,
+        })
+    }
+
+    /// Gracefully shut the client down: ask every worker task spawned for it
+    /// to stop, close the `WaitMap` so in-flight and future `send_resp` calls
+    /// resolve with [`HostErr::Disconnected`] instead of hanging, and wait for
+    /// the worker task(s) to actually finish.
+    pub async fn shutdown(&self) {
+        self.request_shutdown();
+        for handle in self.ctx.take_handles() {
+            handle.join().await;
+        }
+    }
+
+    /// Like [`HostClient::shutdown`], but abort the worker task(s) instead of
+    /// waiting for them to finish on their own.
+    pub fn close(&self) {
+        self.request_shutdown();
+        for handle in self.ctx.take_handles() {
+            handle.abort();
         }
     }
 
+    fn request_shutdown(&self) {
+        self.ctx.set_disconnect_reason(DisconnectReason::Shutdown);
+        self.ctx.shutdown.cancel();
+        self.ctx.map.close();
+    }
+
     /// Publish a [Topic] [Message][Topic::Message].
     ///
     /// There is no feedback if the server received our message. If the I/O worker is
     /// closed, an error is returned.
+    ///
+    /// `seq_no` need not be unique: concurrent publishes (even to the same
+    /// topic) are free to reuse one, since nothing ever waits on a reply to
+    /// correlate by it.
     pub async fn publish<T: Topic>(&self, seq_no: u32, msg: &T::Message) -> Result<(), IoClosed>
     where
         T::Message: Serialize,
     {
         let smsg = postcard::to_stdvec(msg).expect("alloc should never fail");
+        let header = WireHeader {
+            key: T::TOPIC_KEY,
+            seq_no,
+        };
+        // Publishes are fire-and-forget: nothing ever replies to this header.
+        // Transports that open a dedicated stream per frame (e.g. `quic`)
+        // consult this to avoid waiting for a reply that will never come.
+        self.ctx.mark_one_way(header.clone());
         self.out
-            .send(RpcFrame {
-                header: WireHeader {
-                    key: T::TOPIC_KEY,
-                    seq_no,
-                },
-                body: smsg,
-            })
+            .send(RpcFrame { header, body: smsg })
             .await
             .map_err(|_| IoClosed)
     }
@@ -277,6 +464,7 @@ where
             .await
             .map_err(|_| IoClosed)?;
         Ok(Subscription {
+            ctx: self.ctx.clone(),
             rx,
             _pd: PhantomData,
         })
@@ -285,6 +473,7 @@ where
 
 /// A structure that represents a subscription to the given topic
 pub struct Subscription<M> {
+    ctx: Arc<HostContext>,
     rx: Receiver<RpcFrame>,
     _pd: PhantomData<M>,
 }
@@ -295,7 +484,8 @@ where
 {
     /// Await a message for the given subscription.
     ///
-    /// Returns [None]` if the subscription was closed
+    /// Returns [None]` if the subscription was closed. If the link went down,
+    /// [`Subscription::disconnect_reason`] will report why.
     pub async fn recv(&mut self) -> Option<M> {
         loop {
             let frame = self.rx.recv().await?;
@@ -304,6 +494,64 @@ where
             }
         }
     }
+
+    /// The reason the link went down, if `recv()` returned [None] because of a
+    /// disconnect rather than the subscription simply being dropped.
+    pub fn disconnect_reason(&self) -> Option<Arc<DisconnectReason>> {
+        self.ctx.disconnect_reason()
+    }
+}
+
+/// One chunk delivered on a [StreamHandle], tagged by which key it arrived on.
+enum StreamItem {
+    /// A chunk that should decode as the endpoint's response type.
+    Data(Vec<u8>),
+    /// A chunk that should decode as `WireErr`, signalling the end of the stream.
+    Err(Vec<u8>),
+}
+
+/// A handle to a multi-frame streaming response, started by
+/// [`HostClient::send_resp_stream`].
+pub struct StreamHandle<M, WireErr> {
+    ctx: Arc<HostContext>,
+    resp_header: WireHeader,
+    err_header: WireHeader,
+    rx: Receiver<StreamItem>,
+    _pd: PhantomData<fn() -> (M, WireErr)>,
+}
+
+impl<M, WireErr> StreamHandle<M, WireErr>
+where
+    M: DeserializeOwned,
+    WireErr: DeserializeOwned,
+{
+    /// Await the next chunk of the stream.
+    ///
+    /// Returns [None] once the server has ended the stream.
+    pub async fn recv(&mut self) -> Option<Result<M, HostErr<WireErr>>> {
+        loop {
+            match self.rx.recv().await? {
+                StreamItem::Data(body) => {
+                    if let Ok(m) = postcard::from_bytes(&body) {
+                        return Some(Ok(m));
+                    }
+                    // Ignore chunks that fail to decode, same as `Subscription::recv`.
+                }
+                StreamItem::Err(body) => {
+                    return Some(match postcard::from_bytes::<WireErr>(&body) {
+                        Ok(e) => Err(HostErr::Wire(e)),
+                        Err(e) => Err(HostErr::Postcard(e)),
+                    });
+                }
+            }
+        }
+    }
+}
+
+impl<M, WireErr> Drop for StreamHandle<M, WireErr> {
+    fn drop(&mut self) {
+        self.ctx.remove_stream(&self.resp_header, &self.err_header);
+    }
 }
 
 // Manual Clone impl because WireErr may not impl Clone
@@ -358,6 +606,46 @@ impl RpcFrame {
 pub struct HostContext {
     map: WaitMap<WireHeader, Vec<u8>>,
     seq: AtomicU32,
+    streams: Mutex<HashMap<WireHeader, StreamSlot>>,
+    /// Set at most once: by the I/O worker right before it exits, or by
+    /// [`HostClient::shutdown`]/[`HostClient::close`]. The first reason set wins.
+    disconnect: OnceLock<Arc<DisconnectReason>>,
+    /// Cancelled when a caller asks for a graceful shutdown, so worker tasks
+    /// (built-in or custom) can stop on their own terms. A `CancellationToken`
+    /// (rather than a `Notify`) is used so the signal is never missed: unlike
+    /// `Notify::notify_waiters`, cancellation is persisted, so it's observed
+    /// by `wait_for_shutdown` regardless of whether the worker was already
+    /// parked on it when `cancel()` was called.
+    shutdown: CancellationToken,
+    /// Handles to every I/O worker task spawned for this context, collected
+    /// by [`HostClient::shutdown`]/[`HostClient::close`].
+    handles: Mutex<Vec<TaskHandle>>,
+    /// Reference counts of in-flight [`HostClient::publish`] frames, keyed
+    /// by [`WireHeader`], nothing ever replies to. Consulted (and
+    /// decremented) by transports that open a dedicated stream per outgoing
+    /// frame, so they know not to wait for a reply that will never arrive.
+    ///
+    /// A count rather than a plain set, since `publish`'s `seq_no` is
+    /// caller-supplied: concurrent publishes to the same topic can share a
+    /// `seq_no`/header, and a set would let the first `take_one_way` call
+    /// consume the only entry out from under the others.
+    one_way: Mutex<HashMap<WireHeader, usize>>,
+    /// One-shot senders for every `send_resp`/`send_resp_dyn` call currently
+    /// waiting on a reply, keyed by `seq_no`. Reconnecting transports (e.g.
+    /// `ws`) fire these via [`HostContext::fail_pending`] when the link
+    /// drops, so a caller fails promptly with [`HostErr::Disconnected`]/
+    /// [`HostErr::Closed`] instead of hanging for a reply that may never
+    /// arrive on a dead connection.
+    pending: Mutex<HashMap<u32, tokio::sync::oneshot::Sender<()>>>,
+}
+
+/// The bookkeeping [HostContext] keeps per open [StreamHandle], registered under
+/// both its response and error [WireHeader]s so either one can be routed to it.
+#[derive(Clone)]
+struct StreamSlot {
+    tx: Sender<StreamItem>,
+    resp_key: Key,
+    err_key: Key,
 }
 
 /// The I/O worker has closed.
@@ -376,6 +664,9 @@ impl HostContext {
     /// Like `HostContext::process` but tells you if we processed the message or
     /// nobody wanted it
     pub fn process_did_wake(&self, frame: RpcFrame) -> Result<bool, ProcessError> {
+        let Some(frame) = self.dispatch_stream(frame) else {
+            return Ok(true);
+        };
         match self.map.wake(&frame.header, frame.body) {
             WakeOutcome::Woke => Ok(true),
             WakeOutcome::NoMatch(_) => Ok(false),
@@ -387,10 +678,147 @@ impl HostContext {
     ///
     /// Returns an Err if the map was closed.
     pub fn process(&self, frame: RpcFrame) -> Result<(), ProcessError> {
+        let Some(frame) = self.dispatch_stream(frame) else {
+            return Ok(());
+        };
         if let WakeOutcome::Closed(_) = self.map.wake(&frame.header, frame.body) {
             Err(ProcessError::Closed)
         } else {
             Ok(())
         }
     }
+
+    /// Register a [StreamHandle]'s sender under both its response and error
+    /// [WireHeader]s, so either one routes incoming frames to it.
+    fn register_stream(&self, resp_header: WireHeader, err_header: WireHeader, tx: Sender<StreamItem>) {
+        let slot = StreamSlot {
+            tx,
+            resp_key: resp_header.key,
+            err_key: err_header.key,
+        };
+        let mut streams = self.streams.lock().unwrap();
+        streams.insert(resp_header, slot.clone());
+        streams.insert(err_header, slot);
+    }
+
+    /// Deregister a [StreamHandle] that was dropped, so a dead consumer stops
+    /// the server-side producer from leaking an entry.
+    fn remove_stream(&self, resp_header: &WireHeader, err_header: &WireHeader) {
+        let mut streams = self.streams.lock().unwrap();
+        streams.remove(resp_header);
+        streams.remove(err_header);
+    }
+
+    /// Record why the link went down. The worker should call this exactly
+    /// once, right before it exits. Only the first call takes effect.
+    pub fn set_disconnect_reason(&self, reason: DisconnectReason) {
+        let _ = self.disconnect.set(Arc::new(reason));
+    }
+
+    /// The reason the link went down, if the worker has reported one yet.
+    pub fn disconnect_reason(&self) -> Option<Arc<DisconnectReason>> {
+        self.disconnect.get().cloned()
+    }
+
+    /// Register a spawned I/O worker task, so [`HostClient::shutdown`]/
+    /// [`HostClient::close`] have something to await or abort.
+    pub fn register_task(&self, handle: TaskHandle) {
+        self.handles.lock().unwrap().push(handle);
+    }
+
+    /// Resolves once a caller has asked for a graceful shutdown via
+    /// [`HostClient::shutdown`] or [`HostClient::close`]. Custom I/O workers
+    /// built on [`WireContext`] should race this alongside their own I/O to
+    /// exit promptly.
+    pub async fn wait_for_shutdown(&self) {
+        self.shutdown.cancelled().await;
+    }
+
+    fn take_handles(&self) -> Vec<TaskHandle> {
+        std::mem::take(&mut *self.handles.lock().unwrap())
+    }
+
+    /// Record that `header` belongs to a one-way [`HostClient::publish`] frame.
+    fn mark_one_way(&self, header: WireHeader) {
+        *self.one_way.lock().unwrap().entry(header).or_insert(0) += 1;
+    }
+
+    /// Check whether `header` was marked one-way, consuming one mark if so.
+    ///
+    /// Consumes (rather than clears) the mark so that, if concurrent
+    /// `publish` calls happen to share a `header`, each `drive_request`
+    /// only takes credit for the one frame it's actually driving.
+    fn take_one_way(&self, header: &WireHeader) -> bool {
+        let mut one_way = self.one_way.lock().unwrap();
+        match one_way.get_mut(header) {
+            Some(count) if *count > 1 => {
+                *count -= 1;
+                true
+            }
+            Some(_) => {
+                one_way.remove(header);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Register a `send_resp`/`send_resp_dyn` call as waiting on a reply,
+    /// returning the receiving half [`HostContext::fail_pending`] will fire
+    /// if the link drops before one arrives.
+    fn register_pending(&self, seq_no: u32) -> tokio::sync::oneshot::Receiver<()> {
+        let (tx, rx) = tokio::sync::oneshot::channel();
+        self.pending.lock().unwrap().insert(seq_no, tx);
+        rx
+    }
+
+    /// Stop tracking `seq_no` once its `send_resp`/`send_resp_dyn` call has
+    /// resolved, one way or another.
+    fn unregister_pending(&self, seq_no: u32) {
+        self.pending.lock().unwrap().remove(&seq_no);
+    }
+
+    /// Fail every currently in-flight `send_resp`/`send_resp_dyn` call
+    /// promptly instead of leaving it to hang waiting for a reply that will
+    /// never arrive on a dead connection. Called by a reconnecting
+    /// transport (e.g. `ws`) when its link drops. The woken callers see
+    /// [`HostErr::Disconnected`] if a reason has already been set via
+    /// [`HostContext::set_disconnect_reason`], and [`HostErr::Closed`]
+    /// otherwise, as after a plain reconnect.
+    pub(crate) fn fail_pending(&self) {
+        for tx in std::mem::take(&mut *self.pending.lock().unwrap()).into_values() {
+            let _ = tx.send(());
+        }
+    }
+
+    /// If `frame` matches a registered [StreamHandle], forward it there and
+    /// return [None]. Otherwise hand `frame` back unchanged for the normal
+    /// [WaitMap] path.
+    fn dispatch_stream(&self, frame: RpcFrame) -> Option<RpcFrame> {
+        let mut streams = self.streams.lock().unwrap();
+        let Some(slot) = streams.get(&frame.header).cloned() else {
+            drop(streams);
+            return Some(frame);
+        };
+        let sibling_key = if frame.header.key == slot.err_key {
+            slot.resp_key
+        } else {
+            slot.err_key
+        };
+        let sibling = WireHeader {
+            seq_no: frame.header.seq_no,
+            key: sibling_key,
+        };
+        if frame.header.key == slot.err_key {
+            let _ = slot.tx.try_send(StreamItem::Err(frame.body));
+            streams.remove(&frame.header);
+            streams.remove(&sibling);
+        } else if frame.body.is_empty() {
+            streams.remove(&frame.header);
+            streams.remove(&sibling);
+        } else {
+            let _ = slot.tx.try_send(StreamItem::Data(frame.body));
+        }
+        None
+    }
 }
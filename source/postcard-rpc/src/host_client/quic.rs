@@ -0,0 +1,206 @@
+//! QUIC transport for [HostClient], built on `quinn`
+//!
+//! Unlike the other transports, which multiplex every request through a
+//! single wire and rely entirely on the `seq_no`-keyed [`WaitMap`][maitake_sync::WaitMap]
+//! to tell responses apart, QUIC gives each request its own bidirectional
+//! stream for free. [`HostClient::new_quic()`] takes advantage of that: every
+//! outgoing [`RpcFrame`] opens a fresh stream, is written there, and the
+//! frame(s) written back on that same stream (including a multi-frame
+//! [`StreamHandle`][super::StreamHandle] reply, terminated by the error key
+//! or an empty frame)
+//! are read until the peer closes its send side. Head-of-line blocking
+//! between unrelated requests, and the `Decode`/`Io` ambiguity a single
+//! shared stream would have, both go away.
+//!
+//! Since a QUIC stream carries a byte stream rather than pre-framed
+//! messages, each frame on a bidirectional stream is still prefixed with a 4-byte
+//! little-endian length, the same scheme [`io::LengthDelimitedCodec`][super::io::LengthDelimitedCodec]
+//! uses.
+//!
+//! Topic publishes still go out over their own per-frame bidirectional
+//! stream rather than a unidirectional stream or datagram, but the reply
+//! half is never read for them: [`HostClient::publish`][super::HostClient::publish]
+//! marks its frame's header as one-way up front, so the worker writes it,
+//! finishes the send side, and moves on instead of waiting on a reply that
+//! will never come. Topic messages pushed *from* the server arrive on
+//! unidirectional streams, one per message, each holding a single frame
+//! with no length prefix.
+
+use std::net::SocketAddr;
+
+use postcard::experimental::schema::Schema;
+use quinn::{Connection, Endpoint, RecvStream, SendStream};
+use serde::de::DeserializeOwned;
+
+use super::{HostClient, HostContext, RpcFrame, SubInfo, TaskHandle, WireContext};
+use crate::WireHeader;
+
+impl<WireErr> HostClient<WireErr>
+where
+    WireErr: DeserializeOwned + Schema,
+{
+    /// Create a new [HostClient] connected via QUIC to `server_addr`.
+    ///
+    /// `endpoint` is used to dial the connection; `server_name` is the
+    /// name checked against the peer's certificate. Each request opens its
+    /// own bidirectional stream rather than sharing one wire.
+    pub fn new_quic(
+        endpoint: Endpoint,
+        server_addr: SocketAddr,
+        server_name: &str,
+        err_uri_path: &str,
+        outgoing_depth: usize,
+    ) -> Self {
+        let (me, wire) = Self::new_manual(err_uri_path, outgoing_depth);
+        let handle = tokio::spawn(run(endpoint, server_addr, server_name.to_string(), wire));
+        me.ctx.register_task(TaskHandle::Tokio(handle));
+        me
+    }
+}
+
+async fn run(endpoint: Endpoint, server_addr: SocketAddr, server_name: String, wire: WireContext) {
+    let WireContext {
+        mut outgoing,
+        incoming,
+        mut new_subs,
+    } = wire;
+
+    let connecting = match endpoint.connect(server_addr, &server_name) {
+        Ok(connecting) => connecting,
+        Err(e) => {
+            incoming.set_disconnect_reason(super::DisconnectReason::Io(format!("{e}")));
+            return;
+        }
+    };
+    let connection = tokio::select! {
+        conn = connecting => match conn {
+            Ok(conn) => conn,
+            Err(e) => {
+                incoming.set_disconnect_reason(super::DisconnectReason::Io(format!("{e}")));
+                return;
+            }
+        },
+        _ = incoming.wait_for_shutdown() => return,
+    };
+
+    let mut subs: Vec<SubInfo> = Vec::new();
+
+    loop {
+        tokio::select! {
+            _ = incoming.wait_for_shutdown() => {
+                return;
+            }
+            frame = outgoing.recv() => {
+                let Some(frame) = frame else {
+                    incoming.set_disconnect_reason(super::DisconnectReason::AllClientsDropped);
+                    return;
+                };
+                spawn_request(&connection, &incoming, frame);
+            }
+            sub = new_subs.recv() => {
+                let Some(sub) = sub else {
+                    incoming.set_disconnect_reason(super::DisconnectReason::AllClientsDropped);
+                    return;
+                };
+                subs.push(sub);
+            }
+            uni = connection.accept_uni() => {
+                let mut recv = match uni {
+                    Ok(recv) => recv,
+                    Err(e) => {
+                        incoming.set_disconnect_reason(super::DisconnectReason::Io(format!("{e}")));
+                        return;
+                    }
+                };
+                let Ok(data) = recv.read_to_end(MAX_FRAME_LEN).await else {
+                    continue;
+                };
+                if let Some(frame) = decode_frame(&data) {
+                    subs.retain(|sub| {
+                        sub.key != frame.header.key
+                            || sub
+                                .tx
+                                .try_send(RpcFrame {
+                                    header: frame.header.clone(),
+                                    body: frame.body.clone(),
+                                })
+                                .is_ok()
+                    });
+                }
+            }
+        }
+    }
+}
+
+/// Largest single frame `read_to_end`/length-delimited reads will accept.
+const MAX_FRAME_LEN: usize = 16 * 1024 * 1024;
+
+/// Open a bidirectional stream for one outgoing frame, write it, and read
+/// back every reply frame the peer sends on the same stream. Runs as its
+/// own task so one slow request can't hold up the others; it races the
+/// shared shutdown signal so it doesn't outlive a closed [HostClient].
+fn spawn_request(connection: &Connection, ctx: &std::sync::Arc<HostContext>, frame: RpcFrame) {
+    let connection = connection.clone();
+    let ctx = ctx.clone();
+    tokio::spawn(async move {
+        tokio::select! {
+            _ = drive_request(connection, &ctx, frame) => {}
+            _ = ctx.wait_for_shutdown() => {}
+        }
+    });
+}
+
+async fn drive_request(connection: Connection, ctx: &HostContext, frame: RpcFrame) {
+    let one_way = ctx.take_one_way(&frame.header);
+
+    let (mut send, mut recv) = match connection.open_bi().await {
+        Ok(streams) => streams,
+        Err(_) => return,
+    };
+    if write_frame(&mut send, &frame).await.is_err() {
+        return;
+    }
+    let _ = send.finish();
+
+    if one_way {
+        // A topic publish: nothing ever replies, so don't hold the stream
+        // (and this task) open waiting for one.
+        return;
+    }
+
+    while let Some(frame) = read_frame(&mut recv).await {
+        let _ = ctx.process_did_wake(frame);
+    }
+}
+
+async fn write_frame(send: &mut SendStream, frame: &RpcFrame) -> std::io::Result<()> {
+    let raw = frame.to_bytes();
+    let len = (raw.len() as u32).to_le_bytes();
+    send.write_all(&len)
+        .await
+        .map_err(|e| std::io::Error::other(e.to_string()))?;
+    send.write_all(&raw)
+        .await
+        .map_err(|e| std::io::Error::other(e.to_string()))?;
+    Ok(())
+}
+
+async fn read_frame(recv: &mut RecvStream) -> Option<RpcFrame> {
+    let mut len_buf = [0u8; 4];
+    recv.read_exact(&mut len_buf).await.ok()?;
+    let len = u32::from_le_bytes(len_buf) as usize;
+    if len > MAX_FRAME_LEN {
+        return None;
+    }
+    let mut body = vec![0u8; len];
+    recv.read_exact(&mut body).await.ok()?;
+    decode_frame(&body)
+}
+
+fn decode_frame(data: &[u8]) -> Option<RpcFrame> {
+    let (header, body) = postcard::take_from_bytes::<WireHeader>(data).ok()?;
+    Some(RpcFrame {
+        header,
+        body: body.to_vec(),
+    })
+}
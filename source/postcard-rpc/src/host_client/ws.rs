@@ -0,0 +1,337 @@
+//! WebSocket transport for [HostClient]
+//!
+//! This lets a [HostClient] reach a postcard-rpc device over a WebSocket
+//! bridge instead of local USB/serial. On native targets the socket is
+//! driven by `tokio-tungstenite`; on `wasm32` it is driven by
+//! `ws_stream_wasm`, behind the same [`HostClient::new_ws()`] entry point.
+//!
+//! The spawned I/O task reconnects on socket error with a growing backoff.
+//! Every topic ever subscribed to is kept and re-forwarded on the new
+//! connection, so a caller's [`Subscription`] is never aware that the link
+//! dropped. Requests, however, do not survive a reconnect: a dropped socket
+//! fails every `send_resp`/`send_resp_dyn` call still waiting on a reply
+//! instead of carrying it over to the new connection, since there's no way
+//! to know whether the original request frame ever reached the peer. A
+//! reconnect attempt alone doesn't report a [`DisconnectReason`] (it isn't a
+//! worker exit, and the reason is only ever latched once), so those calls
+//! see a plain [`HostErr::Closed`] unless the worker has genuinely given up
+//! (all [`HostClient`]s dropped, or [`HostClient::shutdown`]/[`HostClient::close`]
+//! was called), in which case it's [`HostErr::Disconnected`].
+//!
+//! [`Subscription`]: super::Subscription
+
+use std::{sync::Arc, time::Duration};
+
+use postcard::experimental::schema::Schema;
+use serde::de::DeserializeOwned;
+
+use super::{HostClient, HostContext, RpcFrame, SubInfo, WireContext};
+use crate::WireHeader;
+
+/// The smallest backoff between reconnect attempts.
+const MIN_BACKOFF: Duration = Duration::from_millis(250);
+/// The largest backoff between reconnect attempts.
+const MAX_BACKOFF: Duration = Duration::from_secs(10);
+
+/// # WebSocket Constructor
+impl<WireErr> HostClient<WireErr>
+where
+    WireErr: DeserializeOwned + Schema,
+{
+    /// Create a new [HostClient] connected via a WebSocket to `url`.
+    ///
+    /// A worker task is spawned that drives the socket, automatically
+    /// reconnecting with backoff if the link drops. Live topic subscriptions
+    /// are kept and resume once the new connection is up; requests still in
+    /// flight at the time of a drop instead fail immediately, since a
+    /// request frame that may or may not have reached the peer can't safely
+    /// be replayed on the new connection.
+    pub fn new_ws(url: &str, err_uri_path: &str, outgoing_depth: usize) -> Self {
+        let (me, wire) = Self::new_manual(err_uri_path, outgoing_depth);
+        imp::spawn(url.to_string(), wire, &me.ctx);
+        me
+    }
+}
+
+/// Bookkeeping shared by the native and wasm worker loops.
+struct WsWorker {
+    ctx: Arc<HostContext>,
+    /// Every [SubInfo] ever registered. Kept (rather than recreated) across
+    /// reconnects so callers never need to re-subscribe.
+    subs: Vec<SubInfo>,
+}
+
+impl WsWorker {
+    fn new(ctx: Arc<HostContext>) -> Self {
+        Self {
+            ctx,
+            subs: Vec::new(),
+        }
+    }
+
+    /// Record every newly-registered subscription without discarding the
+    /// ones registered on an earlier connection.
+    fn absorb_new_subs(&mut self, new_subs: &mut tokio::sync::mpsc::Receiver<SubInfo>) {
+        while let Ok(sub) = new_subs.try_recv() {
+            self.subs.push(sub);
+        }
+    }
+
+    /// Forward an inbound frame to the shared [HostContext], falling back
+    /// to topic subscribers if nothing was waiting on it.
+    fn on_frame(&mut self, frame: RpcFrame) {
+        let header = frame.header.clone();
+        if matches!(self.ctx.process_did_wake(frame.clone_for_sub()), Ok(false) | Err(_)) {
+            self.subs.retain(|sub| {
+                sub.key != header.key || sub.tx.try_send(frame.clone_for_sub()).is_ok()
+            });
+        }
+    }
+
+    /// The socket died: fail every `send_resp`/`send_resp_dyn` call still
+    /// waiting on a reply instead of leaving it to hang, since there's no
+    /// way to know whether its request frame reached the peer before the
+    /// link dropped.
+    fn disconnect(&mut self) {
+        self.ctx.fail_pending();
+    }
+}
+
+impl RpcFrame {
+    fn clone_for_sub(&self) -> Self {
+        RpcFrame {
+            header: self.header.clone(),
+            body: self.body.clone(),
+        }
+    }
+}
+
+/// Step the backoff up to [MAX_BACKOFF], starting from [MIN_BACKOFF].
+fn next_backoff(current: Duration) -> Duration {
+    (current * 2).min(MAX_BACKOFF)
+}
+
+#[cfg(not(target_family = "wasm"))]
+mod imp {
+    use super::*;
+    use futures_util::{SinkExt, StreamExt};
+    use tokio::sync::mpsc::Receiver;
+    use tokio_tungstenite::tungstenite::Message;
+
+    pub(super) fn spawn(url: String, wire: WireContext, ctx: &super::HostContext) {
+        let handle = tokio::spawn(run(url, wire));
+        ctx.register_task(super::TaskHandle::Tokio(handle));
+    }
+
+    async fn run(url: String, wire: WireContext) {
+        let WireContext {
+            mut outgoing,
+            incoming,
+            mut new_subs,
+        } = wire;
+        let mut worker = WsWorker::new(incoming);
+        let mut backoff = MIN_BACKOFF;
+
+        loop {
+            worker.absorb_new_subs(&mut new_subs);
+
+            let (stream, _resp) = tokio::select! {
+                conn = tokio_tungstenite::connect_async(&url) => match conn {
+                    Ok(conn) => conn,
+                    Err(_) => {
+                        // Just a failed reconnect attempt, not a worker exit:
+                        // don't latch a reason into the `OnceLock` here, or a
+                        // later real disconnect could never overwrite it.
+                        tokio::time::sleep(backoff).await;
+                        backoff = next_backoff(backoff);
+                        continue;
+                    }
+                },
+                _ = worker.ctx.wait_for_shutdown() => return,
+            };
+            backoff = MIN_BACKOFF;
+            let (mut tx, mut rx) = stream.split();
+
+            let res = drive_one_connection(&mut worker, &mut tx, &mut rx, &mut outgoing, &mut new_subs).await;
+            worker.disconnect();
+            if res.is_none() {
+                // All `HostClient`s were dropped, or shutdown was requested;
+                // either way the worker is exiting for good.
+                return;
+            }
+        }
+    }
+
+    async fn drive_one_connection<S, R>(
+        worker: &mut WsWorker,
+        tx: &mut S,
+        rx: &mut R,
+        outgoing: &mut Receiver<RpcFrame>,
+        new_subs: &mut Receiver<SubInfo>,
+    ) -> Option<()>
+    where
+        S: futures_util::Sink<Message> + Unpin,
+        R: futures_util::Stream<Item = Result<Message, tokio_tungstenite::tungstenite::Error>> + Unpin,
+    {
+        loop {
+            tokio::select! {
+                _ = worker.ctx.wait_for_shutdown() => {
+                    return None;
+                }
+                frame = outgoing.recv() => {
+                    let Some(frame) = frame else {
+                        // All `HostClient`s were dropped: this is a genuine
+                        // worker exit, so it's safe to latch the reason.
+                        worker.ctx.set_disconnect_reason(super::DisconnectReason::AllClientsDropped);
+                        return None;
+                    };
+                    // A send/recv error here just ends this connection; the
+                    // worker loop reconnects rather than exiting, so the
+                    // reason is deliberately *not* set here (see the
+                    // `OnceLock` contract on `HostContext::disconnect`).
+                    if tx.send(Message::Binary(frame.to_bytes())).await.is_err() {
+                        return Some(());
+                    }
+                }
+                sub = new_subs.recv() => {
+                    let Some(sub) = sub else {
+                        worker.ctx.set_disconnect_reason(super::DisconnectReason::AllClientsDropped);
+                        return None;
+                    };
+                    worker.subs.push(sub);
+                }
+                msg = rx.next() => {
+                    match msg {
+                        Some(Ok(Message::Binary(data))) => {
+                            if let Some(frame) = decode_frame(&data) {
+                                worker.on_frame(frame);
+                            }
+                        }
+                        Some(Ok(_)) => {}
+                        Some(Err(_)) | None => {
+                            return Some(());
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    fn decode_frame(data: &[u8]) -> Option<RpcFrame> {
+        let (header, body) = postcard::take_from_bytes::<WireHeader>(data).ok()?;
+        Some(RpcFrame {
+            header,
+            body: body.to_vec(),
+        })
+    }
+}
+
+#[cfg(target_family = "wasm")]
+mod imp {
+    use super::*;
+    use futures_util::StreamExt;
+    use tokio::sync::mpsc::Receiver;
+    use ws_stream_wasm::{WsMessage, WsMeta};
+
+    pub(super) fn spawn(url: String, wire: WireContext, ctx: &super::HostContext) {
+        wasm_bindgen_futures::spawn_local(run(url, wire));
+        ctx.register_task(super::TaskHandle::Wasm);
+    }
+
+    async fn run(url: String, wire: WireContext) {
+        let WireContext {
+            mut outgoing,
+            incoming,
+            mut new_subs,
+        } = wire;
+        let mut worker = WsWorker::new(incoming);
+        let mut backoff = MIN_BACKOFF;
+
+        loop {
+            worker.absorb_new_subs(&mut new_subs);
+
+            let (_meta, mut stream) = tokio::select! {
+                conn = WsMeta::connect(&url, None) => match conn {
+                    Ok(conn) => conn,
+                    Err(_) => {
+                        // Just a failed reconnect attempt, not a worker exit:
+                        // don't latch a reason into the `OnceLock` here, or a
+                        // later real disconnect could never overwrite it.
+                        gloo_timers::future::sleep(backoff).await;
+                        backoff = next_backoff(backoff);
+                        continue;
+                    }
+                },
+                _ = worker.ctx.wait_for_shutdown() => return,
+            };
+            backoff = MIN_BACKOFF;
+
+            let res = drive_one_connection(&mut worker, &mut stream, &mut outgoing, &mut new_subs).await;
+            worker.disconnect();
+            if res.is_none() {
+                // All `HostClient`s were dropped, or shutdown was requested;
+                // either way the worker is exiting for good.
+                return;
+            }
+        }
+    }
+
+    async fn drive_one_connection(
+        worker: &mut WsWorker,
+        stream: &mut (impl futures_util::Sink<WsMessage> + futures_util::Stream<Item = WsMessage> + Unpin),
+        outgoing: &mut Receiver<RpcFrame>,
+        new_subs: &mut Receiver<SubInfo>,
+    ) -> Option<()> {
+        use futures_util::SinkExt;
+        loop {
+            tokio::select! {
+                _ = worker.ctx.wait_for_shutdown() => {
+                    return None;
+                }
+                frame = outgoing.recv() => {
+                    let Some(frame) = frame else {
+                        // All `HostClient`s were dropped: this is a genuine
+                        // worker exit, so it's safe to latch the reason.
+                        worker.ctx.set_disconnect_reason(super::DisconnectReason::AllClientsDropped);
+                        return None;
+                    };
+                    // A send/recv error here just ends this connection; the
+                    // worker loop reconnects rather than exiting, so the
+                    // reason is deliberately *not* set here (see the
+                    // `OnceLock` contract on `HostContext::disconnect`).
+                    if stream.send(WsMessage::Binary(frame.to_bytes())).await.is_err() {
+                        return Some(());
+                    }
+                }
+                sub = new_subs.recv() => {
+                    let Some(sub) = sub else {
+                        worker.ctx.set_disconnect_reason(super::DisconnectReason::AllClientsDropped);
+                        return None;
+                    };
+                    worker.subs.push(sub);
+                }
+                msg = stream.next() => {
+                    match msg {
+                        Some(WsMessage::Binary(data)) => {
+                            if let Some(frame) = decode_frame(&data) {
+                                worker.on_frame(frame);
+                            }
+                        }
+                        Some(WsMessage::Text(_)) => {}
+                        None => {
+                            return Some(());
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    fn decode_frame(data: &[u8]) -> Option<RpcFrame> {
+        let (header, body) = postcard::take_from_bytes::<WireHeader>(data).ok()?;
+        Some(RpcFrame {
+            header,
+            body: body.to_vec(),
+        })
+    }
+}